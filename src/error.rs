@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors that can occur while talking to a JSON-RPC node or estimating gas.
+#[derive(Debug, Error)]
+pub enum GasEstimatorError {
+    /// The node rejected the call, e.g. insufficient funds or execution reverted.
+    #[error("RPC error {code}: {message}")]
+    Rpc {
+        code: i64,
+        message: String,
+        /// Extra data the node attached to the error, e.g. an encoded revert reason.
+        data: Option<Value>,
+    },
+    /// The HTTP client failed before a JSON-RPC response was received.
+    #[error("transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    /// The response body could not be decoded into the expected shape.
+    #[error("failed to decode RPC response: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// The node returned a well-formed response that didn't make sense, e.g. an
+    /// unparsable hex value or neither a `result` nor an `error`. Websocket and IPC
+    /// connection and I/O failures are reported with this variant as well.
+    #[error("invalid response from node: {0}")]
+    InvalidResponse(String),
+    /// The transport was misconfigured, e.g. no endpoint URL was supplied.
+    #[error("invalid gas estimator configuration: {0}")]
+    Config(String),
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct JsonRpcError {
+    pub(crate) code: i64,
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) data: Option<Value>,
+}
+
+impl From<JsonRpcError> for GasEstimatorError {
+    fn from(err: JsonRpcError) -> Self {
+        GasEstimatorError::Rpc {
+            code: err.code,
+            message: err.message,
+            data: err.data,
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use serde::Deserialize;
+
+use alloy_primitives::U256;
+
+use crate::error::GasEstimatorError;
+use crate::send_rpc;
+use crate::transport::JsonRpcTransport;
+
+/// The percentile of in-block priority fees requested from `eth_feeHistory`.
+/// The median balances responsiveness against outlier tips from a single block.
+const REWARD_PERCENTILE: f64 = 50.0;
+
+#[derive(Debug, Deserialize)]
+struct FeeHistory {
+    #[serde(rename = "baseFeePerGas")]
+    base_fee_per_gas: Vec<String>,
+    reward: Option<Vec<Vec<String>>>,
+}
+
+/// Suggested EIP-1559 fee parameters derived from recent fee history.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub base_fee: U256,
+}
+
+/// Suggests `maxFeePerGas`/`maxPriorityFeePerGas` for an EIP-1559 transaction by
+/// sampling `eth_feeHistory` over the last `block_count` blocks.
+///
+/// `maxPriorityFeePerGas` is the median of the per-block 50th-percentile priority
+/// fee, and `maxFeePerGas` is `2 * next_base_fee + maxPriorityFeePerGas`, where
+/// `next_base_fee` is the forward-looking base fee `eth_feeHistory` appends as the
+/// last entry of `baseFeePerGas`.
+pub async fn suggest_fees<Tr: JsonRpcTransport>(
+    transport: &Tr,
+    block_count: u64,
+) -> Result<FeeSuggestion, GasEstimatorError> {
+    let params = vec![
+        serde_json::json!(format!("0x{block_count:x}")),
+        serde_json::json!("latest"),
+        serde_json::json!([REWARD_PERCENTILE]),
+    ];
+
+    let history: FeeHistory = send_rpc(transport, "eth_feeHistory", params).await?;
+
+    let base_fee = history
+        .base_fee_per_gas
+        .last()
+        .ok_or_else(|| {
+            GasEstimatorError::InvalidResponse(
+                "eth_feeHistory returned no baseFeePerGas entries".to_string(),
+            )
+        })
+        .and_then(|hex| parse_hex_u256(hex))?;
+
+    let rewards = history.reward.ok_or_else(|| {
+        GasEstimatorError::InvalidResponse("eth_feeHistory returned no reward samples".to_string())
+    })?;
+
+    let mut samples = rewards
+        .iter()
+        .map(|block_rewards| {
+            block_rewards
+                .first()
+                .ok_or_else(|| {
+                    GasEstimatorError::InvalidResponse(
+                        "eth_feeHistory reward entry was empty".to_string(),
+                    )
+                })
+                .and_then(|hex| parse_hex_u256(hex))
+        })
+        .collect::<Result<Vec<U256>, _>>()?;
+    samples.sort();
+
+    let max_priority_fee_per_gas = *samples.get(samples.len() / 2).ok_or_else(|| {
+        GasEstimatorError::InvalidResponse("eth_feeHistory returned no blocks".to_string())
+    })?;
+
+    let max_fee_per_gas = base_fee * U256::from(2) + max_priority_fee_per_gas;
+
+    Ok(FeeSuggestion {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        base_fee,
+    })
+}
+
+fn parse_hex_u256(value: &str) -> Result<U256, GasEstimatorError> {
+    U256::from_str_radix(value.trim_start_matches("0x"), 16)
+        .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use serde_json::Value;
+
+    use super::*;
+
+    /// A transport that answers `eth_feeHistory` with a fixed, synthetic history
+    /// instead of talking to a node.
+    struct FakeFeeHistoryTransport {
+        base_fee_per_gas: Vec<&'static str>,
+        reward: Vec<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl JsonRpcTransport for FakeFeeHistoryTransport {
+        async fn request<T>(&self, method: &str, _params: Vec<Value>) -> Result<T, GasEstimatorError>
+        where
+            T: for<'de> Deserialize<'de>,
+        {
+            assert_eq!(method, "eth_feeHistory");
+            let body = serde_json::json!({
+                "baseFeePerGas": self.base_fee_per_gas,
+                "gasUsedRatio": [],
+                "reward": self.reward,
+            });
+            Ok(serde_json::from_value(body)?)
+        }
+    }
+
+    #[test]
+    fn parse_hex_u256_accepts_0x_prefixed_values() {
+        assert_eq!(parse_hex_u256("0x1a").unwrap(), U256::from(26));
+    }
+
+    #[tokio::test]
+    async fn suggest_fees_uses_next_base_fee_and_median_tip() {
+        let transport = FakeFeeHistoryTransport {
+            // 100, 110, 120 wei; the forward-looking entry (120) is the next base fee.
+            base_fee_per_gas: vec!["0x64", "0x6e", "0x78"],
+            // per-block 50th-percentile tips: 10, 20, 30 -> median 20.
+            reward: vec![vec!["0xa"], vec!["0x14"], vec!["0x1e"]],
+        };
+
+        let fees = suggest_fees(&transport, 3).await.expect("should succeed");
+
+        assert_eq!(fees.base_fee, U256::from(120));
+        assert_eq!(fees.max_priority_fee_per_gas, U256::from(20));
+        assert_eq!(fees.max_fee_per_gas, U256::from(120 * 2 + 20));
+    }
+
+    #[tokio::test]
+    async fn suggest_fees_rejects_empty_history() {
+        let transport = FakeFeeHistoryTransport {
+            base_fee_per_gas: vec![],
+            reward: vec![],
+        };
+
+        let err = suggest_fees(&transport, 3).await.unwrap_err();
+        assert!(matches!(err, GasEstimatorError::InvalidResponse(_)));
+    }
+}
@@ -1,79 +1,115 @@
-use std::fmt;
+use std::collections::HashMap;
 
-use serde::{Deserialize, Serialize};
-
-use thiserror::Error;
-
-use reqwest::Client;
-
-use lazy_static::lazy_static;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
 
 use alloy_primitives::Address;
 use alloy_primitives::U256;
 
-const INFURA_KEY: &str = "cfdfffb93d6a470e97b67bf871f8a347";
-
-lazy_static! {
-    static ref RPC_URL: String = format!("https://mainnet.infura.io/v3/{INFURA_KEY}");
+mod error;
+mod fee;
+mod transport;
+
+pub use error::GasEstimatorError;
+pub use fee::{suggest_fees, FeeSuggestion};
+pub use transport::{
+    HttpTransport, HttpTransportBuilder, IpcTransport, JsonRpcTransport, WsTransport,
+};
+
+/// A public mainnet endpoint, for local experimentation only. Opt in with the
+/// `mainnet-default` feature and an `INFURA_KEY` environment variable; production
+/// users should build an [`HttpTransport`] pointed at their own authenticated node
+/// via [`HttpTransport::builder`] instead.
+#[cfg(feature = "mainnet-default")]
+lazy_static::lazy_static! {
+    static ref RPC_URL: String = {
+        let key = std::env::var("INFURA_KEY")
+            .expect("INFURA_KEY must be set to use the mainnet-default feature");
+        format!("https://mainnet.infura.io/v3/{key}")
+    };
 }
 
-#[derive(Serialize)]
-struct JsonRpcRequest<'a> {
-    jsonrpc: &'a str,
-    method: &'a str,
+pub(crate) async fn send_rpc<Tr, T>(
+    transport: &Tr,
+    method: &str,
     params: Vec<serde_json::Value>,
-    id: u64,
+) -> Result<T, GasEstimatorError>
+where
+    Tr: JsonRpcTransport,
+    T: for<'de> Deserialize<'de>,
+{
+    transport.request(method, params).await
 }
 
-#[derive(Debug, Deserialize)]
-struct JsonRpcResponse<T> {
-    result: Option<T>,
-    error: Option<JsonRpcError>,
+/// The block a call is evaluated against. Accepted wherever a node expects a
+/// block parameter.
+#[derive(Debug, Clone)]
+pub enum BlockTag {
+    Number(u64),
+    Latest,
+    Pending,
+    Hash(String),
 }
 
-#[derive(Deserialize, Debug, Error)]
-struct JsonRpcError {
-    code: i64,
-    message: String,
+impl Serialize for BlockTag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            BlockTag::Number(n) => serializer.serialize_str(&format!("0x{n:x}")),
+            BlockTag::Latest => serializer.serialize_str("latest"),
+            BlockTag::Pending => serializer.serialize_str("pending"),
+            // Per EIP-1898, a block hash is only valid as `{"blockHash": "0x..."}`,
+            // not as a bare string like the number/tag variants.
+            BlockTag::Hash(hash) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("blockHash", hash)?;
+                map.end()
+            }
+        }
+    }
 }
 
-// Implement fmt::Display
-impl fmt::Display for JsonRpcError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "JSON-RPC Error {}: {}", self.code, self.message)
+#[cfg(test)]
+mod block_tag_tests {
+    use super::BlockTag;
+
+    #[test]
+    fn serializes_number_as_hex_quantity() {
+        assert_eq!(
+            serde_json::to_value(BlockTag::Number(255)).unwrap(),
+            serde_json::json!("0xff")
+        );
     }
-}
 
-async fn send_rpc<T: for<'de> Deserialize<'de>>(
-    rpc_url: &str,
-    method: &str,
-    params: Vec<serde_json::Value>,
-) -> Result<T, Box<dyn std::error::Error>> {
-    let client = Client::new();
-    let request = JsonRpcRequest {
-        jsonrpc: "2.0",
-        method,
-        params,
-        id: 1,
-    };
+    #[test]
+    fn serializes_latest_and_pending_as_bare_tags() {
+        assert_eq!(
+            serde_json::to_value(BlockTag::Latest).unwrap(),
+            serde_json::json!("latest")
+        );
+        assert_eq!(
+            serde_json::to_value(BlockTag::Pending).unwrap(),
+            serde_json::json!("pending")
+        );
+    }
 
-    let response = client
-        .post(rpc_url)
-        .json(&request)
-        .send()
-        .await?
-        .json::<JsonRpcResponse<T>>()
-        .await?;
-
-    if let Some(result) = response.result {
-        Ok(result)
-    } else if let Some(error) = response.error {
-        Err(format!("RPC Error: {} - {}", error.code, error.message).into())
-    } else {
-        Err("Unknown RPC Error".into())
+    #[test]
+    fn serializes_hash_as_eip1898_object() {
+        let hash = "0xabc123".to_string();
+        assert_eq!(
+            serde_json::to_value(BlockTag::Hash(hash.clone())).unwrap(),
+            serde_json::json!({ "blockHash": hash })
+        );
     }
 }
 
+/// Per-address account overrides (balance, code, storage) applied for the
+/// duration of a single call, keyed by the `0x`-prefixed address string.
+pub type StateOverrides = HashMap<String, Value>;
+
 // We call it "Embellished" because the "from" field has been recovered from v,r,s.
 #[derive(Debug, Serialize)]
 pub struct Transaction {
@@ -87,14 +123,26 @@ pub struct Transaction {
     pub v: u64, // Recovery ID
     pub r: U256,
     pub s: U256,
+    /// EIP-1559 fee cap, as suggested by [`suggest_fees`]. `None` for a legacy
+    /// transaction using `gas_price` instead.
+    pub max_fee_per_gas: Option<U256>,
+    /// EIP-1559 tip, as suggested by [`suggest_fees`].
+    pub max_priority_fee_per_gas: Option<U256>,
 }
 
-pub async fn estimate_gas(
+/// Estimates the gas required to execute `tx`.
+///
+/// `block` selects the state `eth_estimateGas` evaluates against, defaulting to
+/// `"latest"` when omitted. `state_overrides` lets the call be estimated against
+/// hypothetical balances, code, or storage without mutating chain state.
+pub async fn estimate_gas<Tr: JsonRpcTransport>(
     tx: &Transaction,
-    rpc_url: &str,
-) -> Result<U256, Box<dyn std::error::Error>> {
+    transport: &Tr,
+    block: Option<BlockTag>,
+    state_overrides: Option<&StateOverrides>,
+) -> Result<U256, GasEstimatorError> {
     // Construct JSON-RPC parameters
-    let params = serde_json::json!(
+    let call = serde_json::json!(
         {
             "from": tx.from.to_string(),
             "to": tx.to.to_string(),
@@ -103,15 +151,21 @@ pub async fn estimate_gas(
         }
     );
 
-    let gas_estimate: String = send_rpc(&rpc_url, "eth_estimateGas", vec![params]).await?;
+    let mut params = vec![call];
+    if block.is_some() || state_overrides.is_some() {
+        params.push(serde_json::to_value(block.unwrap_or(BlockTag::Latest))?);
+    }
+    if let Some(overrides) = state_overrides {
+        params.push(serde_json::to_value(overrides)?);
+    }
 
-    Ok(U256::from_str_radix(
-        gas_estimate.trim_start_matches("0x"),
-        16,
-    )?)
+    let gas_estimate: String = send_rpc(transport, "eth_estimateGas", params).await?;
+
+    U256::from_str_radix(gas_estimate.trim_start_matches("0x"), 16)
+        .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "mainnet-default"))]
 mod tests {
     use super::*;
     use std::str::FromStr;
@@ -134,11 +188,17 @@ mod tests {
             v: 777,
             r: U256::from(987654321),
             s: U256::from(121212),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
         };
 
+        let transport = HttpTransport::new(RPC_URL.as_str());
+
         let expected = U256::from(21_000);
         assert_eq!(
-            estimate_gas(&tx, &RPC_URL).await.expect("should succeed"),
+            estimate_gas(&tx, &transport, None, None)
+                .await
+                .expect("should succeed"),
             expected
         );
     }
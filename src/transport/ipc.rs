@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::sync::Mutex;
+
+use crate::error::GasEstimatorError;
+
+use super::{IdGenerator, JsonRpcRequest, JsonRpcResponse, JsonRpcTransport};
+
+/// Sends JSON-RPC requests over a Unix domain socket, the usual way to reach a
+/// node running on the same host (e.g. geth's `geth.ipc`).
+pub struct IpcTransport {
+    socket: Mutex<BufReader<UnixStream>>,
+    ids: IdGenerator,
+}
+
+impl IpcTransport {
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, GasEstimatorError> {
+        let stream = UnixStream::connect(path)
+            .await
+            .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))?;
+
+        Ok(Self {
+            socket: Mutex::new(BufReader::new(stream)),
+            ids: IdGenerator::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl JsonRpcTransport for IpcTransport {
+    async fn request<T>(&self, method: &str, params: Vec<Value>) -> Result<T, GasEstimatorError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let id = self.ids.next();
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            method,
+            params,
+            id,
+        };
+        let mut payload = serde_json::to_vec(&request)?;
+        payload.push(b'\n');
+
+        let mut socket = self.socket.lock().await;
+        socket
+            .get_mut()
+            .write_all(&payload)
+            .await
+            .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))?;
+
+        loop {
+            let mut line = String::new();
+            socket
+                .read_line(&mut line)
+                .await
+                .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))?;
+
+            let response: JsonRpcResponse<T> = serde_json::from_str(&line)?;
+            if response.id == Some(id) {
+                return response.into_result();
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{GasEstimatorError, JsonRpcError};
+
+mod http;
+mod ipc;
+mod ws;
+
+pub use http::{HttpTransport, HttpTransportBuilder};
+pub use ipc::IpcTransport;
+pub use ws::WsTransport;
+
+/// Hands out monotonically increasing JSON-RPC request ids.
+///
+/// Every transport owns one of these so that concurrent or multiplexed calls over
+/// the same connection (most importantly websockets) can match each reply back to
+/// the request that caused it, instead of assuming replies arrive in order.
+pub(crate) struct IdGenerator(AtomicU64);
+
+impl IdGenerator {
+    pub(crate) fn new() -> Self {
+        Self(AtomicU64::new(1))
+    }
+
+    pub(crate) fn next(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct JsonRpcRequest<'a> {
+    pub(crate) jsonrpc: &'a str,
+    pub(crate) method: &'a str,
+    pub(crate) params: Vec<Value>,
+    pub(crate) id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct JsonRpcResponse<T> {
+    #[serde(default)]
+    pub(crate) id: Option<u64>,
+    pub(crate) result: Option<T>,
+    pub(crate) error: Option<JsonRpcError>,
+}
+
+impl<T> JsonRpcResponse<T> {
+    pub(crate) fn into_result(self) -> Result<T, GasEstimatorError> {
+        if let Some(result) = self.result {
+            Ok(result)
+        } else if let Some(error) = self.error {
+            Err(error.into())
+        } else {
+            Err(GasEstimatorError::InvalidResponse(
+                "RPC response contained neither a result nor an error".to_string(),
+            ))
+        }
+    }
+}
+
+/// A JSON-RPC transport capable of sending a single request and decoding its result.
+///
+/// Implementations own their connection (an HTTP client, a persistent websocket, a
+/// Unix socket) and are free to reuse it across calls, which keeps the gas-estimation
+/// logic above this trait completely unaware of how bytes actually reach the node.
+#[async_trait]
+pub trait JsonRpcTransport {
+    async fn request<T>(&self, method: &str, params: Vec<Value>) -> Result<T, GasEstimatorError>
+    where
+        T: for<'de> Deserialize<'de>;
+}
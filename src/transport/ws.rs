@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::stream::SplitSink;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio::sync::{oneshot, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::error::GasEstimatorError;
+
+use super::{IdGenerator, JsonRpcRequest, JsonRpcResponse, JsonRpcTransport};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type PendingReplies = Arc<Mutex<HashMap<u64, oneshot::Sender<String>>>>;
+
+/// Sends JSON-RPC requests over a single persistent websocket connection.
+///
+/// A background task owns the read half and dispatches each incoming frame to
+/// whichever call is waiting on its id, so concurrent `request` calls are
+/// pipelined over the connection instead of queuing behind each other's replies.
+pub struct WsTransport {
+    sink: Mutex<SplitSink<WsStream, Message>>,
+    pending: PendingReplies,
+    ids: IdGenerator,
+}
+
+impl WsTransport {
+    pub async fn connect(url: &str) -> Result<Self, GasEstimatorError> {
+        let (stream, _) = connect_async(url)
+            .await
+            .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))?;
+
+        let (sink, stream) = stream.split();
+        let pending: PendingReplies = Arc::new(Mutex::new(HashMap::new()));
+
+        tokio::spawn(read_replies(stream, pending.clone()));
+
+        Ok(Self {
+            sink: Mutex::new(sink),
+            pending,
+            ids: IdGenerator::new(),
+        })
+    }
+}
+
+/// Reads frames off the socket for the lifetime of the connection, handing each
+/// one to the caller awaiting its id. Frames with no matching (or no) id are
+/// dropped, e.g. unsolicited subscription notifications.
+async fn read_replies(
+    mut stream: futures_util::stream::SplitStream<WsStream>,
+    pending: PendingReplies,
+) {
+    while let Some(message) = stream.next().await {
+        let Ok(Message::Text(text)) = message else {
+            continue;
+        };
+        let Some(id) = response_id(&text) else {
+            continue;
+        };
+        if let Some(reply_tx) = pending.lock().await.remove(&id) {
+            let _ = reply_tx.send(text);
+        }
+    }
+    // The connection is gone: drop every pending sender so waiting calls fail
+    // instead of hanging.
+    pending.lock().await.clear();
+}
+
+fn response_id(text: &str) -> Option<u64> {
+    #[derive(Deserialize)]
+    struct IdOnly {
+        id: Option<u64>,
+    }
+    serde_json::from_str::<IdOnly>(text).ok()?.id
+}
+
+#[async_trait]
+impl JsonRpcTransport for WsTransport {
+    async fn request<T>(&self, method: &str, params: Vec<Value>) -> Result<T, GasEstimatorError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let id = self.ids.next();
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            method,
+            params,
+            id,
+        };
+        let payload = serde_json::to_string(&request)?;
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, reply_tx);
+
+        if let Err(e) = self
+            .sink
+            .lock()
+            .await
+            .send(Message::Text(payload))
+            .await
+            .map_err(|e| GasEstimatorError::InvalidResponse(e.to_string()))
+        {
+            self.pending.lock().await.remove(&id);
+            return Err(e);
+        }
+
+        let text = reply_rx.await.map_err(|_| {
+            GasEstimatorError::InvalidResponse(
+                "websocket connection closed before a response was received".to_string(),
+            )
+        })?;
+
+        let response: JsonRpcResponse<T> = serde_json::from_str(&text)?;
+        response.into_result()
+    }
+}